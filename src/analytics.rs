@@ -0,0 +1,171 @@
+use crate::{parse_price, Product};
+
+/// Aggregate statistics computed over a batch of scraped products.
+#[derive(Debug)]
+pub struct Analytics {
+    pub mean_price: Option<f64>,
+    pub median_price: Option<f64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub avg_rating: Option<f64>,
+    pub total_reviews: u64,
+    /// `(bucket_low, bucket_high, count)` buckets, low to high.
+    pub price_histogram: Vec<(f64, f64, usize)>,
+}
+
+const HISTOGRAM_BUCKETS: usize = 5;
+
+pub fn analyze(products: &[Product]) -> Analytics {
+    let mut prices: Vec<f64> = products
+        .iter()
+        .filter_map(|p| parse_price(&p.price))
+        .collect();
+    prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mean_price = if prices.is_empty() {
+        None
+    } else {
+        Some(prices.iter().sum::<f64>() / prices.len() as f64)
+    };
+
+    let ratings: Vec<f64> = products
+        .iter()
+        .filter_map(|p| parse_rating(&p.rating_text))
+        .collect();
+    let avg_rating = if ratings.is_empty() {
+        None
+    } else {
+        Some(ratings.iter().sum::<f64>() / ratings.len() as f64)
+    };
+
+    let total_reviews: u64 = products
+        .iter()
+        .filter_map(|p| parse_review_count(&p.review_count))
+        .sum();
+
+    Analytics {
+        mean_price,
+        median_price: median(&prices),
+        min_price: prices.first().copied(),
+        max_price: prices.last().copied(),
+        avg_rating,
+        total_reviews,
+        price_histogram: histogram(&prices, HISTOGRAM_BUCKETS),
+    }
+}
+
+fn median(sorted_prices: &[f64]) -> Option<f64> {
+    if sorted_prices.is_empty() {
+        return None;
+    }
+    let mid = sorted_prices.len() / 2;
+    Some(if sorted_prices.len().is_multiple_of(2) {
+        (sorted_prices[mid - 1] + sorted_prices[mid]) / 2.0
+    } else {
+        sorted_prices[mid]
+    })
+}
+
+/// Parse the leading number out of `"4.6 out of 5 stars"`.
+fn parse_rating(rating_text: &str) -> Option<f64> {
+    rating_text.split_whitespace().next()?.parse().ok()
+}
+
+/// Parse `"12,345 ratings"` into `12345`, stripping commas and the label.
+fn parse_review_count(review_count: &str) -> Option<u64> {
+    let digits: String = review_count.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        None
+    } else {
+        digits.parse().ok()
+    }
+}
+
+fn histogram(sorted_prices: &[f64], buckets: usize) -> Vec<(f64, f64, usize)> {
+    if sorted_prices.is_empty() {
+        return Vec::new();
+    }
+    let min = sorted_prices[0];
+    let max = *sorted_prices.last().unwrap();
+    let width = ((max - min) / buckets as f64).max(0.01);
+
+    let mut counts = vec![0usize; buckets];
+    for &price in sorted_prices {
+        let idx = (((price - min) / width) as usize).min(buckets - 1);
+        counts[idx] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| (min + i as f64 * width, min + (i + 1) as f64 * width, count))
+        .collect()
+}
+
+pub fn print_summary(analytics: &Analytics) {
+    println!("\n📊 Aggregate analytics:");
+    match (
+        analytics.min_price,
+        analytics.max_price,
+        analytics.mean_price,
+        analytics.median_price,
+    ) {
+        (Some(min), Some(max), Some(mean), Some(median)) => println!(
+            "  price: min ${:.2}  max ${:.2}  mean ${:.2}  median ${:.2}",
+            min, max, mean, median
+        ),
+        _ => println!("  price: no parsable prices"),
+    }
+
+    match analytics.avg_rating {
+        Some(r) => println!("  average rating: {:.2} / 5", r),
+        None => println!("  average rating: n/a"),
+    }
+
+    println!("  total reviews: {}", analytics.total_reviews);
+
+    if !analytics.price_histogram.is_empty() {
+        println!("  price histogram:");
+        for (low, high, count) in &analytics.price_histogram {
+            println!("    ${:.2}–${:.2}: {}", low, high, "#".repeat(*count));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rating_reads_leading_number() {
+        assert_eq!(parse_rating("4.6 out of 5 stars"), Some(4.6));
+        assert_eq!(parse_rating("not a rating"), None);
+    }
+
+    #[test]
+    fn parse_review_count_strips_commas_and_label() {
+        assert_eq!(parse_review_count("12,345 ratings"), Some(12345));
+        assert_eq!(parse_review_count("no reviews"), None);
+    }
+
+    #[test]
+    fn median_handles_even_and_odd_lengths() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[1.0, 2.0, 3.0]), Some(2.0));
+        assert_eq!(median(&[1.0, 2.0, 3.0, 4.0]), Some(2.5));
+    }
+
+    #[test]
+    fn histogram_buckets_prices_across_the_range() {
+        let prices = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let buckets = histogram(&prices, 2);
+        assert_eq!(buckets.len(), 2);
+        let total: usize = buckets.iter().map(|(_, _, count)| count).sum();
+        assert_eq!(total, prices.len());
+    }
+
+    #[test]
+    fn histogram_is_empty_for_no_prices() {
+        assert!(histogram(&[], 5).is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::marketplace::Marketplace;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Crawl configuration loaded from a TOML file.
+///
+/// `selectors` overrides the hardcoded defaults per site, keyed by
+/// marketplace host (e.g. `"www.amazon.com"`, `"www.amazon.fr"`).
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    /// Search queries/URLs to crawl, in order.
+    pub queries: Vec<String>,
+    /// Max results to keep per query.
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+    /// Where to write the exported products. Falls back to the format's
+    /// default path when unset.
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub selectors: HashMap<String, SiteSelectors>,
+    /// Country codes to search when `--search` is used (e.g. `["US", "FR"]`).
+    /// Entries that don't match `Marketplace::from_country_code` are skipped;
+    /// an empty/unset list, or one where no entry matches, falls back to
+    /// every marketplace in `Marketplace::ALL`.
+    #[serde(default)]
+    pub marketplaces: Vec<String>,
+}
+
+fn default_max_results() -> usize {
+    10
+}
+
+/// Per-site selector overrides. Any field left unset keeps that parser's
+/// hardcoded default.
+#[derive(Debug, Deserialize, Default)]
+pub struct SiteSelectors {
+    pub item: Option<String>,
+    #[serde(default)]
+    pub title: Vec<String>,
+    #[serde(default)]
+    pub link: Vec<String>,
+    pub price: Option<String>,
+    pub rating: Option<String>,
+    pub review_count: Option<String>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("read config failed: {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("parse config failed: {}", path.display()))
+    }
+
+    /// Used when no `--config` is passed: crawl a single query with no
+    /// selector overrides.
+    pub fn single_query(url: &str) -> Self {
+        Self {
+            queries: vec![url.to_string()],
+            max_results: default_max_results(),
+            output: None,
+            selectors: HashMap::new(),
+            marketplaces: Vec::new(),
+        }
+    }
+
+    /// Marketplaces to search when `--search` is used, resolved from
+    /// `marketplaces`. Falls back to `Marketplace::ALL` when that list is
+    /// empty or none of its codes resolve.
+    pub fn search_marketplaces(&self) -> Vec<Marketplace> {
+        let resolved: Vec<Marketplace> = self
+            .marketplaces
+            .iter()
+            .filter_map(|code| Marketplace::from_country_code(code))
+            .collect();
+        if resolved.is_empty() {
+            Marketplace::ALL.to_vec()
+        } else {
+            resolved
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_marketplaces_defaults_to_all_when_empty() {
+        let config = Config::single_query("https://www.amazon.com/s?k=laptop");
+        assert_eq!(config.search_marketplaces(), Marketplace::ALL.to_vec());
+    }
+
+    #[test]
+    fn search_marketplaces_resolves_configured_country_codes() {
+        let mut config = Config::single_query("https://www.amazon.com/s?k=laptop");
+        config.marketplaces = vec!["fr".to_string(), "gb".to_string()];
+        assert_eq!(
+            config.search_marketplaces(),
+            vec![Marketplace::Fr, Marketplace::Gb]
+        );
+    }
+
+    #[test]
+    fn search_marketplaces_falls_back_when_every_code_is_invalid() {
+        let mut config = Config::single_query("https://www.amazon.com/s?k=laptop");
+        config.marketplaces = vec!["zz".to_string()];
+        assert_eq!(config.search_marketplaces(), Marketplace::ALL.to_vec());
+    }
+}
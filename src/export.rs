@@ -0,0 +1,166 @@
+use crate::Product;
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::Path;
+use zip::write::FileOptions;
+
+/// Output format selectable via `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Ods,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Some(Format::Csv),
+            "ods" => Some(Format::Ods),
+            _ => None,
+        }
+    }
+
+    pub fn default_path(self) -> &'static str {
+        match self {
+            Format::Csv => "products.csv",
+            Format::Ods => "products.ods",
+        }
+    }
+}
+
+const HEADER: [&str; 6] = ["title", "link", "price", "currency", "rating_text", "review_count"];
+
+pub fn write(products: &[Product], format: Format, path: &Path) -> Result<()> {
+    match format {
+        Format::Csv => write_csv(products, path),
+        Format::Ods => write_ods(products, path),
+    }
+}
+
+fn write_csv(products: &[Product], path: &Path) -> Result<()> {
+    let mut wtr = csv::Writer::from_path(path)
+        .with_context(|| format!("open csv writer failed: {}", path.display()))?;
+    wtr.write_record(HEADER).context("write csv header failed")?;
+    for p in products {
+        wtr.write_record([
+            p.title.as_str(),
+            p.link.as_str(),
+            p.price.as_str(),
+            p.marketplace.currency(),
+            p.rating_text.as_str(),
+            p.review_count.as_str(),
+        ])
+        .context("write csv row failed")?;
+    }
+    wtr.flush().context("flush csv writer failed")?;
+    Ok(())
+}
+
+/// Escape text for use inside an XML element body.
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn ods_row(cells: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut row = String::from("<table:table-row>");
+    for cell in cells {
+        row.push_str("<table:table-cell office:value-type=\"string\"><text:p>");
+        row.push_str(&xml_escape(cell.as_ref()));
+        row.push_str("</text:p></table:table-cell>");
+    }
+    row.push_str("</table:table-row>");
+    row
+}
+
+/// Build the flat `content.xml` body for a single-sheet spreadsheet.
+fn ods_content_xml(products: &[Product]) -> String {
+    let mut rows = ods_row(HEADER);
+    for p in products {
+        rows.push_str(&ods_row([
+            p.title.as_str(),
+            p.link.as_str(),
+            p.price.as_str(),
+            p.marketplace.currency(),
+            p.rating_text.as_str(),
+            p.review_count.as_str(),
+        ]));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2">
+<office:body><office:spreadsheet><table:table table:name="Products">{rows}</table:table></office:spreadsheet></office:body>
+</office:document-content>"#
+    )
+}
+
+const ODS_MANIFEST_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+<manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/>
+<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>"#;
+
+const ODS_MIMETYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// Hand-build a minimal ODS file (no external spreadsheet library
+/// supports writing ODS): a zip archive with an uncompressed `mimetype`
+/// entry, a manifest, and a `content.xml` holding one sheet.
+fn write_ods(products: &[Product], path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("create ods file failed: {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    // The mimetype entry must be first and stored (uncompressed) per the
+    // OpenDocument spec, so readers can sniff the format without inflating.
+    zip.start_file("mimetype", FileOptions::default().compression_method(zip::CompressionMethod::Stored))
+        .context("start ods mimetype entry failed")?;
+    zip.write_all(ODS_MIMETYPE.as_bytes())
+        .context("write ods mimetype failed")?;
+
+    zip.start_file("META-INF/manifest.xml", FileOptions::default())
+        .context("start ods manifest entry failed")?;
+    zip.write_all(ODS_MANIFEST_XML.as_bytes())
+        .context("write ods manifest failed")?;
+
+    zip.start_file("content.xml", FileOptions::default())
+        .context("start ods content entry failed")?;
+    zip.write_all(ods_content_xml(products).as_bytes())
+        .context("write ods content failed")?;
+
+    zip.finish().context("finish ods archive failed")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marketplace::Marketplace;
+
+    #[test]
+    fn xml_escape_handles_reserved_characters() {
+        assert_eq!(
+            xml_escape(r#"Tom & Jerry "Fun" <Show>"#),
+            "Tom &amp; Jerry &quot;Fun&quot; &lt;Show&gt;"
+        );
+    }
+
+    #[test]
+    fn ods_content_xml_includes_header_and_rows() {
+        let products = vec![Product {
+            title: "Widget & Gadget".to_string(),
+            link: "https://example.com/dp/1".to_string(),
+            price: "$9.99".to_string(),
+            rating_text: "4.5 out of 5 stars".to_string(),
+            review_count: "123 ratings".to_string(),
+            marketplace: Marketplace::Us,
+        }];
+        let xml = ods_content_xml(&products);
+        assert!(xml.contains("table:name=\"Products\""));
+        assert!(xml.contains("title"));
+        assert!(xml.contains("Widget &amp; Gadget"));
+        assert!(xml.contains("USD"));
+    }
+}
@@ -1,93 +1,87 @@
+mod analytics;
+mod config;
+mod export;
+mod marketplace;
+mod notify;
+mod parser;
+mod ratelimit;
+mod search;
+mod store;
+
 use anyhow::{Context, Result};
-use reqwest::Client;
-use scraper::{ElementRef, Html, Selector};
-use tokio::sync::Semaphore;
+use config::Config;
+use marketplace::Marketplace;
+use ratelimit::RequestGate;
+use reqwest::{Client, Url};
+use std::path::Path;
 use std::sync::Arc;
-fn clean_text(s: &str) -> String {
+use std::time::{SystemTime, UNIX_EPOCH};
+use store::{PriceRecord, PriceStore};
+
+/// Requests per second allowed across the whole crawl (search + details).
+const REQUESTS_PER_SECOND: u32 = 2;
+/// Max number of requests in flight at once.
+const MAX_IN_FLIGHT: usize = 3;
+
+pub(crate) fn clean_text(s: &str) -> String {
     s.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
-fn extract_title(item: &ElementRef, title_sels: &[Selector], img_alt_sel: &Selector) -> Option<String> {
-    // 1) try common title DOMs
-    for sel in title_sels {
-        if let Some(el) = item.select(sel).next() {
-            let t = clean_text(&el.text().collect::<String>());
-            if !t.is_empty() {
-                return Some(t);
-            }
-        }
-    }
-    // 2) fallback: image alt is often the title
-    if let Some(img) = item.select(img_alt_sel).next() {
-        if let Some(alt) = img.value().attr("alt") {
-            let t = clean_text(alt);
-            if !t.is_empty() {
-                return Some(t);
-            }
-        }
-    }
-    None
-}
 #[derive(Debug)]
-struct Detail {
-    rating_text: String,   // e.g. "4.6 out of 5 stars"
-    review_count: String,  // e.g. "12,345 ratings"
-}
-fn extract_link(item: &ElementRef, link_sels: &[Selector]) -> Option<String> {
-    for sel in link_sels {
-        if let Some(a) = item.select(sel).next() {
-            if let Some(href) = a.value().attr("href") {
-                return Some(if href.starts_with("http") {
-                    href.to_string()
-                } else {
-                    format!("https://www.amazon.com{}", href)
-                });
-            }
-        }
-    }
-    None
+pub struct Detail {
+    pub rating_text: String,  // e.g. "4.6 out of 5 stars"
+    pub review_count: String, // e.g. "12,345 ratings"
 }
-async fn fetch_detail(client: &Client, url: &str) -> Result<Detail> {
-    let res = client
-        .get(url)
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .send()
-        .await
-        .with_context(|| format!("request detail failed: {url}"))?;
-
-    let body = res.text().await.context("read detail body failed")?;
-    let doc = Html::parse_document(&body);
-
-    let rating_sel = Selector::parse("span.a-icon-alt").unwrap();
-    let review_count_sel = Selector::parse("#acrCustomerReviewText").unwrap();
 
-    let rating_text = doc
-        .select(&rating_sel)
-        .next()
-        .map(|e| clean_text(&e.text().collect::<String>()))
-        .unwrap_or_else(|| "N/A".to_string());
+/// A fully scraped product row, ready for export and aggregate analytics.
+///
+/// `marketplace` records which storefront `price` was scraped from, so a
+/// price can be converted to a common currency (or just displayed with its
+/// origin) after results from multiple marketplaces have been merged.
+#[derive(Debug, Clone)]
+pub struct Product {
+    pub title: String,
+    pub link: String,
+    pub price: String,
+    pub rating_text: String,
+    pub review_count: String,
+    pub marketplace: Marketplace,
+}
 
-    let review_count = doc
-        .select(&review_count_sel)
-        .next()
-        .map(|e| clean_text(&e.text().collect::<String>()))
-        .unwrap_or_else(|| "N/A".to_string());
+const PRICE_STORE_PATH: &str = "price_history.jsonl";
 
-    Ok(Detail { rating_text, review_count })
+/// One search-result row, before its detail page has been fetched.
+struct Row {
+    title: String,
+    price: String,
+    link: String,
 }
-#[tokio::main]
-async fn main() -> Result<()> {
-    let url = "https://www.amazon.com/s?k=laptop";
 
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36")
-        .build()
-        .context("build client failed")?;
+/// Run one crawl of `url`, printing results, recording/alerting on price
+/// drops via `store`, and collecting the scraped rows for export.
+///
+/// Detail pages are fetched concurrently, bounded by `gate`. At most
+/// `max_results` cards are kept.
+async fn crawl_once(
+    client: &Client,
+    url: &str,
+    max_results: usize,
+    store: &PriceStore,
+    gate: &Arc<RequestGate>,
+    config: &Config,
+    marketplace: Marketplace,
+) -> Result<Vec<Product>> {
+    let target = Url::parse(url).context("invalid target url")?;
+
+    let parsers = parser::registered(config, marketplace);
+    let site = parsers
+        .iter()
+        .find(|p| p.can_parse(&target))
+        .context("no registered SiteParser can handle this url")?
+        .clone();
 
-    let res = client
-        .get(url)
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .send()
+    let res = gate
+        .get(client, url, marketplace.accept_language())
         .await
         .context("request failed")?;
 
@@ -97,75 +91,271 @@ async fn main() -> Result<()> {
     let body = res.text().await.context("read body failed")?;
     std::fs::write("amazon_debug.html", &body).ok();
 
-    let doc = Html::parse_document(&body);
-
-    // Each search result card
-    let item_sel = Selector::parse(r#"div[data-component-type="s-search-result"]"#).unwrap();
-
-    // Title fallbacks
-    let title_sels = vec![
-        Selector::parse("h2 a span").unwrap(),
-        Selector::parse(r#"a.a-link-normal.s-line-clamp-2 span"#).unwrap(),
-        Selector::parse(r#"span.a-size-base-plus.a-color-base.a-text-normal"#).unwrap(),
-        Selector::parse(r#"span.a-size-medium.a-color-base.a-text-normal"#).unwrap(),
-    ];
-    let img_alt_sel = Selector::parse("img.s-image").unwrap();
-
-    // Link fallbacks
-    let link_sels = vec![
-        Selector::parse("h2 a").unwrap(),
-        Selector::parse(r#"a.a-link-normal.s-no-outline"#).unwrap(),
-        Selector::parse(r#"a.a-link-normal[href*="/dp/"]"#).unwrap(),
-    ];
+    let doc = scraper::Html::parse_document(&body);
 
-    // Price (full string like "$563.68")
-    let price_sel = Selector::parse("span.a-price span.a-offscreen").unwrap();
+    println!(
+        "\n📦 Amazon Search Results ({}, skip cards without title):\n",
+        marketplace.currency()
+    );
 
-    println!("\n📦 Amazon Search Results (skip cards without title):\n");
-
-    let mut shown = 0usize;
-    for item in doc.select(&item_sel) {
+    let mut rows = Vec::new();
+    for item in doc.select(site.item_selector()) {
         // ✅ skip cards without title
-        let Some(title) = extract_title(&item, &title_sels, &img_alt_sel) else {
+        let Some(title) = site.extract_title(&item) else {
             continue;
         };
 
-        let price = item
-            .select(&price_sel)
-            .next()
-            .map(|e| clean_text(&e.text().collect::<String>()))
-            .unwrap_or_else(|| "N/A".to_string());
-
-        let link = extract_link(&item, &link_sels).unwrap_or_else(|| "N/A".to_string());
+        let price = site.extract_price(&item).unwrap_or_else(|| "N/A".to_string());
+        let link = site.extract_link(&item).unwrap_or_else(|| "N/A".to_string());
 
-        shown += 1;
-        println!("{:02}. {} — {}", shown, title, price);
+        println!("{:02}. {} — {}", rows.len() + 1, title, price);
         println!("    {}", link);
 
-        if shown >= 10 {
+        rows.push(Row { title, price, link });
+        if rows.len() >= max_results {
             break;
         }
-        let sem = Arc::new(Semaphore::new(3)); // 并发限制：最多同时开 3 个详情页请求
-        let permit = sem.clone().acquire_owned().await?;
-        let detail = fetch_detail(&client, &link).await;
-        drop(permit);
-
-        match detail {
-            Ok(d) => {
-                println!("    rating: {}", d.rating_text);
-                println!("    reviews: {}", d.review_count);
+    }
+
+    if rows.is_empty() {
+        println!("No titled cards found. Open amazon_debug.html and inspect a result card to update selectors.");
+        return Ok(Vec::new());
+    }
+
+    let detail_tasks = rows.iter().map(|row| {
+        let client = client.clone();
+        let gate = Arc::clone(gate);
+        let site = Arc::clone(&site);
+        let link = row.link.clone();
+        tokio::spawn(async move { site.parse_detail(&client, &gate, &link).await })
+    });
+    let details = futures::future::join_all(detail_tasks).await;
+
+    let mut products = Vec::with_capacity(rows.len());
+    for (row, detail) in rows.into_iter().zip(details) {
+        let (rating_text, review_count) = match detail {
+            Ok(Ok(d)) => {
+                println!("    {} rating: {}", row.link, d.rating_text);
+                println!("    {} reviews: {}", row.link, d.review_count);
+                (d.rating_text, d.review_count)
+            }
+            Ok(Err(e)) => {
+                println!("    detail fetch failed for {}: {}", row.link, e);
+                ("N/A".to_string(), "N/A".to_string())
             }
             Err(e) => {
-                println!("    detail fetch failed: {}", e);
+                println!("    detail task panicked for {}: {}", row.link, e);
+                ("N/A".to_string(), "N/A".to_string())
             }
+        };
+
+        if let Err(e) = record_and_alert(
+            store,
+            &row.title,
+            &row.link,
+            &row.price,
+            &rating_text,
+            &review_count,
+        ) {
+            println!("    price history update failed: {}", e);
         }
+
+        products.push(Product {
+            title: row.title,
+            link: row.link,
+            price: row.price,
+            rating_text,
+            review_count,
+            marketplace,
+        });
     }
 
-    if shown == 0 {
-        println!("No titled cards found. Open amazon_debug.html and inspect a result card to update selectors.");
+    Ok(products)
+}
+
+/// Crawl every query in `config`, in order, merging all results into one
+/// list of products.
+async fn crawl_all(
+    client: &Client,
+    config: &Config,
+    store: &PriceStore,
+    gate: &Arc<RequestGate>,
+    marketplace: Marketplace,
+) -> Vec<Product> {
+    let mut products = Vec::new();
+    for query in &config.queries {
+        match crawl_once(
+            client,
+            query,
+            config.max_results,
+            store,
+            gate,
+            config,
+            marketplace,
+        )
+        .await
+        {
+            Ok(found) => products.extend(found),
+            Err(e) => println!("crawl failed for {}: {}", query, e),
+        }
     }
+    products
+}
+
+/// Append this snapshot to `store` and notify if the price dropped since the
+/// last time this product was scraped.
+fn record_and_alert(
+    store: &PriceStore,
+    title: &str,
+    link: &str,
+    price: &str,
+    rating_text: &str,
+    review_count: &str,
+) -> Result<()> {
+    if let Some(old_price) = store.last_price(link)? {
+        if let (Some(old), Some(new)) = (parse_price(&old_price), parse_price(price)) {
+            if new < old {
+                if let Err(e) = notify::notify_price_drop(title, &old_price, price) {
+                    println!("    desktop notification failed: {}", e);
+                }
+                if let Err(e) = notify::email_price_drop(title, &old_price, price) {
+                    println!("    email notification failed: {}", e);
+                }
+            }
+        }
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+
+    store.append(&PriceRecord {
+        title: title.to_string(),
+        link: link.to_string(),
+        price: price.to_string(),
+        rating_text: rating_text.to_string(),
+        review_count: review_count.to_string(),
+        timestamp,
+    })
+}
+
+/// Parse a scraped price string like `"$563.68"` into a plain `f64`.
+pub(crate) fn parse_price(price: &str) -> Option<f64> {
+    price
+        .trim()
+        .trim_start_matches(|c: char| !c.is_ascii_digit() && c != '-')
+        .replace(',', "")
+        .parse()
+        .ok()
+}
+
+/// `--watch <seconds>` re-runs the crawl on a fixed interval instead of once.
+fn watch_interval_secs() -> Option<u64> {
+    cli_flag("--watch").and_then(|v| v.parse().ok())
+}
+
+/// `--format csv|ods` selects the export format (defaults to CSV).
+fn cli_format() -> export::Format {
+    cli_flag("--format")
+        .and_then(|v| export::Format::parse(&v))
+        .unwrap_or(export::Format::Csv)
+}
+
+/// `--country <code>` selects the Amazon marketplace to crawl (defaults to US).
+fn cli_marketplace() -> Marketplace {
+    cli_flag("--country")
+        .and_then(|v| Marketplace::from_country_code(&v))
+        .unwrap_or(Marketplace::Us)
+}
+
+/// `--search <keyword>` switches to cross-marketplace search mode: the
+/// keyword is looked up on every marketplace in `config.search_marketplaces()`
+/// (all four by default, or a `marketplaces` list from `--config`) and the
+/// matches are merged into one ranked list.
+fn cli_search_keyword() -> Option<String> {
+    cli_flag("--search")
+}
+
+fn cli_flag(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+    }
+    None
+}
 
+/// Export the scraped products and print aggregate analytics over them.
+/// `output_override` is `config.output`, if set; otherwise the format's
+/// default path is used.
+fn export_and_analyze(products: &[Product], format: export::Format, output_override: Option<&str>) {
+    let default_path = format.default_path();
+    let path = Path::new(output_override.unwrap_or(default_path));
+    if let Err(e) = export::write(products, format, path) {
+        println!("export failed: {}", e);
+    } else {
+        println!("\nWrote {} products to {}", products.len(), path.display());
+    }
+
+    analytics::print_summary(&analytics::analyze(products));
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let format = cli_format();
+    let marketplace = cli_marketplace();
+    let config = match cli_flag("--config") {
+        Some(path) => Config::load(Path::new(&path))?,
+        None => Config::single_query(marketplace.search_url("laptop")?.as_str()),
+    };
 
+    let client = Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36")
+        .build()
+        .context("build client failed")?;
+
+    let store = PriceStore::new(PRICE_STORE_PATH);
+    let gate = Arc::new(RequestGate::new(REQUESTS_PER_SECOND, MAX_IN_FLIGHT));
 
-    Ok(())
+    if let Some(keyword) = cli_search_keyword() {
+        let products =
+            search::search_all_marketplaces(&client, &gate, &config, &keyword, config.max_results)
+                .await;
+        export_and_analyze(&products, format, config.output.as_deref());
+        return Ok(());
+    }
+
+    match watch_interval_secs() {
+        Some(secs) => {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(secs));
+            loop {
+                ticker.tick().await;
+                let products = crawl_all(&client, &config, &store, &gate, marketplace).await;
+                export_and_analyze(&products, format, config.output.as_deref());
+            }
+        }
+        None => {
+            let products = crawl_all(&client, &config, &store, &gate, marketplace).await;
+            export_and_analyze(&products, format, config.output.as_deref());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_price_strips_currency_symbol_and_commas() {
+        assert_eq!(parse_price("$563.68"), Some(563.68));
+        assert_eq!(parse_price("$1,234.50"), Some(1234.50));
+    }
+
+    #[test]
+    fn parse_price_rejects_unparseable_input() {
+        assert_eq!(parse_price("N/A"), None);
+    }
 }
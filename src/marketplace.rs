@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use reqwest::Url;
+
+/// An Amazon storefront for one country, selectable via `--country`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marketplace {
+    Us,
+    Fr,
+    De,
+    Gb,
+}
+
+impl Marketplace {
+    pub const ALL: [Marketplace; 4] = [Marketplace::Us, Marketplace::Fr, Marketplace::De, Marketplace::Gb];
+
+    /// Parse an ISO country code like `"US"` or `"fr"`.
+    pub fn from_country_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "US" => Some(Marketplace::Us),
+            "FR" => Some(Marketplace::Fr),
+            "DE" => Some(Marketplace::De),
+            "GB" | "UK" => Some(Marketplace::Gb),
+            _ => None,
+        }
+    }
+
+    pub fn host(self) -> &'static str {
+        match self {
+            Marketplace::Us => "www.amazon.com",
+            Marketplace::Fr => "www.amazon.fr",
+            Marketplace::De => "www.amazon.de",
+            Marketplace::Gb => "www.amazon.co.uk",
+        }
+    }
+
+    pub fn accept_language(self) -> &'static str {
+        match self {
+            Marketplace::Us => "en-US,en;q=0.9",
+            Marketplace::Fr => "fr-FR,fr;q=0.9",
+            Marketplace::De => "de-DE,de;q=0.9",
+            Marketplace::Gb => "en-GB,en;q=0.9",
+        }
+    }
+
+    pub fn currency(self) -> &'static str {
+        match self {
+            Marketplace::Us => "USD",
+            Marketplace::Fr | Marketplace::De => "EUR",
+            Marketplace::Gb => "GBP",
+        }
+    }
+
+    /// Fixed approximate rate to convert a price in this marketplace's
+    /// `currency()` into USD, so prices scraped from different storefronts
+    /// can be ranked against each other.
+    pub fn usd_exchange_rate(self) -> f64 {
+        match self {
+            Marketplace::Us => 1.0,
+            Marketplace::Fr | Marketplace::De => 1.08, // EUR -> USD
+            Marketplace::Gb => 1.27,                   // GBP -> USD
+        }
+    }
+
+    /// Build `https://<host>/s?k=<keyword>` for this marketplace.
+    pub fn search_url(self, keyword: &str) -> Result<Url> {
+        let mut url = Url::parse(&format!("https://{}/s", self.host()))
+            .with_context(|| format!("invalid marketplace host: {}", self.host()))?;
+        url.query_pairs_mut().append_pair("k", keyword);
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_country_code_is_case_insensitive() {
+        assert_eq!(Marketplace::from_country_code("us"), Some(Marketplace::Us));
+        assert_eq!(Marketplace::from_country_code("FR"), Some(Marketplace::Fr));
+    }
+
+    #[test]
+    fn from_country_code_accepts_uk_as_an_alias_for_gb() {
+        assert_eq!(Marketplace::from_country_code("UK"), Some(Marketplace::Gb));
+        assert_eq!(Marketplace::from_country_code("GB"), Some(Marketplace::Gb));
+    }
+
+    #[test]
+    fn from_country_code_rejects_unknown_codes() {
+        assert_eq!(Marketplace::from_country_code("ZZ"), None);
+    }
+
+    #[test]
+    fn search_url_builds_a_keyword_query_on_the_marketplace_host() {
+        let url = Marketplace::Fr.search_url("laptop").unwrap();
+        assert_eq!(url.host_str(), Some("www.amazon.fr"));
+        assert_eq!(url.query(), Some("k=laptop"));
+    }
+
+    #[test]
+    fn usd_exchange_rate_is_identity_for_us() {
+        assert_eq!(Marketplace::Us.usd_exchange_rate(), 1.0);
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::{Context, Result};
+use lettre::message::Message;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{SmtpTransport, Transport};
+
+/// Fire a desktop notification announcing a price drop.
+pub fn notify_price_drop(title: &str, old_price: &str, new_price: &str) -> Result<()> {
+    notify_rust::Notification::new()
+        .summary("Price drop")
+        .body(&format!("{title}\n{old_price} → {new_price}"))
+        .show()
+        .context("desktop notification failed")?;
+    Ok(())
+}
+
+/// Email a price-drop alert via SMTP, configured entirely from environment
+/// variables (`SMTP_HOST`, `SMTP_USER`, `SMTP_PASS`, `SMTP_FROM`, `SMTP_TO`).
+/// A no-op when `SMTP_HOST` isn't set, so email alerts stay opt-in.
+pub fn email_price_drop(title: &str, old_price: &str, new_price: &str) -> Result<()> {
+    let host = match std::env::var("SMTP_HOST") {
+        Ok(h) => h,
+        Err(_) => return Ok(()),
+    };
+    let user = std::env::var("SMTP_USER").context("SMTP_USER not set")?;
+    let pass = std::env::var("SMTP_PASS").context("SMTP_PASS not set")?;
+    let from = std::env::var("SMTP_FROM").context("SMTP_FROM not set")?;
+    let to = std::env::var("SMTP_TO").context("SMTP_TO not set")?;
+
+    let email = Message::builder()
+        .from(from.parse().context("invalid SMTP_FROM address")?)
+        .to(to.parse().context("invalid SMTP_TO address")?)
+        .subject(format!("Price drop: {title}"))
+        .body(format!("{title}\n{old_price} → {new_price}"))
+        .context("build email failed")?;
+
+    let mailer = SmtpTransport::relay(&host)
+        .context("build SMTP transport failed")?
+        .credentials(Credentials::new(user, pass))
+        .build();
+
+    mailer.send(&email).context("send email failed")?;
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use scraper::{ElementRef, Html, Selector};
+
+use crate::config::SiteSelectors;
+use crate::marketplace::Marketplace;
+use crate::ratelimit::RequestGate;
+use crate::{clean_text, Detail, Product};
+
+use super::{SearchParser, SiteParser};
+
+const DEFAULT_ITEM_SEL: &str = r#"div[data-component-type="s-search-result"]"#;
+const DEFAULT_TITLE_SELS: &[&str] = &[
+    "h2 a span",
+    r#"a.a-link-normal.s-line-clamp-2 span"#,
+    r#"span.a-size-base-plus.a-color-base.a-text-normal"#,
+    r#"span.a-size-medium.a-color-base.a-text-normal"#,
+];
+const DEFAULT_LINK_SELS: &[&str] = &[
+    "h2 a",
+    r#"a.a-link-normal.s-no-outline"#,
+    r#"a.a-link-normal[href*="/dp/"]"#,
+];
+const DEFAULT_PRICE_SEL: &str = "span.a-price span.a-offscreen";
+const DEFAULT_RATING_SEL: &str = "span.a-icon-alt";
+const DEFAULT_REVIEW_COUNT_SEL: &str = "#acrCustomerReviewText";
+
+/// Falls back to `default` when `custom` is absent or fails to compile, so a
+/// typo in the config can't take the whole parser down.
+fn selector_or_default(custom: Option<&str>, default: &str) -> Selector {
+    custom
+        .and_then(|s| Selector::parse(s).ok())
+        .unwrap_or_else(|| Selector::parse(default).unwrap())
+}
+
+/// Falls back to `defaults` when `custom` is empty, or when every entry in
+/// it fails to compile, so a typo in the config can't take the whole parser
+/// down.
+fn selectors_or_default(custom: &[String], defaults: &[&str]) -> Vec<Selector> {
+    let compiled: Vec<Selector> = custom.iter().filter_map(|s| Selector::parse(s).ok()).collect();
+    if !compiled.is_empty() {
+        return compiled;
+    }
+    defaults.iter().map(|s| Selector::parse(s).unwrap()).collect()
+}
+
+/// `SiteParser` for one Amazon marketplace's search results and product
+/// pages.
+///
+/// All selectors are compiled once in [`Amazon::new`], not re-parsed per
+/// item. `overrides` lets a config file fix selector drift without a
+/// recompile. `marketplace` picks the country storefront (host,
+/// `Accept-Language`, currency).
+pub struct Amazon {
+    marketplace: Marketplace,
+    item_sel: Selector,
+    title_sels: Vec<Selector>,
+    img_alt_sel: Selector,
+    link_sels: Vec<Selector>,
+    price_sel: Selector,
+    rating_sel: Selector,
+    review_count_sel: Selector,
+}
+
+impl Amazon {
+    pub fn new(overrides: Option<&SiteSelectors>, marketplace: Marketplace) -> Self {
+        let empty = Vec::new();
+        let (item, title, link, price, rating, review_count) = match overrides {
+            Some(o) => (
+                o.item.as_deref(),
+                o.title.as_slice(),
+                o.link.as_slice(),
+                o.price.as_deref(),
+                o.rating.as_deref(),
+                o.review_count.as_deref(),
+            ),
+            None => (None, empty.as_slice(), empty.as_slice(), None, None, None),
+        };
+
+        Self {
+            marketplace,
+            item_sel: selector_or_default(item, DEFAULT_ITEM_SEL),
+            title_sels: selectors_or_default(title, DEFAULT_TITLE_SELS),
+            img_alt_sel: Selector::parse("img.s-image").unwrap(),
+            link_sels: selectors_or_default(link, DEFAULT_LINK_SELS),
+            price_sel: selector_or_default(price, DEFAULT_PRICE_SEL),
+            rating_sel: selector_or_default(rating, DEFAULT_RATING_SEL),
+            review_count_sel: selector_or_default(review_count, DEFAULT_REVIEW_COUNT_SEL),
+        }
+    }
+}
+
+#[async_trait]
+impl SiteParser for Amazon {
+    fn can_parse(&self, url: &Url) -> bool {
+        url.host_str()
+            .map(|h| h.ends_with(self.marketplace.host()))
+            .unwrap_or(false)
+    }
+
+    fn item_selector(&self) -> &Selector {
+        &self.item_sel
+    }
+
+    fn extract_title(&self, item: &ElementRef) -> Option<String> {
+        // 1) try common title DOMs
+        for sel in &self.title_sels {
+            if let Some(el) = item.select(sel).next() {
+                let t = clean_text(&el.text().collect::<String>());
+                if !t.is_empty() {
+                    return Some(t);
+                }
+            }
+        }
+        // 2) fallback: image alt is often the title
+        if let Some(img) = item.select(&self.img_alt_sel).next() {
+            if let Some(alt) = img.value().attr("alt") {
+                let t = clean_text(alt);
+                if !t.is_empty() {
+                    return Some(t);
+                }
+            }
+        }
+        None
+    }
+
+    fn extract_link(&self, item: &ElementRef) -> Option<String> {
+        for sel in &self.link_sels {
+            if let Some(a) = item.select(sel).next() {
+                if let Some(href) = a.value().attr("href") {
+                    return Some(if href.starts_with("http") {
+                        href.to_string()
+                    } else {
+                        format!("https://{}{}", self.marketplace.host(), href)
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn extract_price(&self, item: &ElementRef) -> Option<String> {
+        item.select(&self.price_sel)
+            .next()
+            .map(|e| clean_text(&e.text().collect::<String>()))
+    }
+
+    async fn parse_detail(&self, client: &Client, gate: &RequestGate, url: &str) -> Result<Detail> {
+        let res = gate
+            .get(client, url, self.marketplace.accept_language())
+            .await
+            .with_context(|| format!("request detail failed: {url}"))?;
+
+        let body = res.text().await.context("read detail body failed")?;
+        let doc = Html::parse_document(&body);
+
+        let rating_text = doc
+            .select(&self.rating_sel)
+            .next()
+            .map(|e| clean_text(&e.text().collect::<String>()))
+            .unwrap_or_else(|| "N/A".to_string());
+
+        let review_count = doc
+            .select(&self.review_count_sel)
+            .next()
+            .map(|e| clean_text(&e.text().collect::<String>()))
+            .unwrap_or_else(|| "N/A".to_string());
+
+        Ok(Detail {
+            rating_text,
+            review_count,
+        })
+    }
+}
+
+#[async_trait]
+impl SearchParser for Amazon {
+    async fn search(
+        &self,
+        client: &Client,
+        gate: &RequestGate,
+        keyword: &str,
+        max_results: usize,
+    ) -> Result<Vec<Product>> {
+        let url = self.marketplace.search_url(keyword)?;
+        let res = gate
+            .get(client, url.as_str(), self.marketplace.accept_language())
+            .await
+            .with_context(|| format!("search request failed: {url}"))?;
+
+        let body = res.text().await.context("read search body failed")?;
+        let doc = Html::parse_document(&body);
+
+        let mut products = Vec::new();
+        for item in doc.select(&self.item_sel) {
+            let Some(title) = self.extract_title(&item) else {
+                continue;
+            };
+            let price = self.extract_price(&item).unwrap_or_else(|| "N/A".to_string());
+            let link = self.extract_link(&item).unwrap_or_else(|| "N/A".to_string());
+
+            products.push(Product {
+                title,
+                link,
+                price,
+                rating_text: "N/A".to_string(),
+                review_count: "N/A".to_string(),
+                marketplace: self.marketplace,
+            });
+            if products.len() >= max_results {
+                break;
+            }
+        }
+        Ok(products)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectors_or_default_uses_defaults_when_custom_is_empty() {
+        let sels = selectors_or_default(&[], DEFAULT_TITLE_SELS);
+        assert_eq!(sels.len(), DEFAULT_TITLE_SELS.len());
+    }
+
+    #[test]
+    fn selectors_or_default_falls_back_when_every_custom_entry_is_invalid() {
+        let custom = vec!["[[[not valid css".to_string()];
+        let sels = selectors_or_default(&custom, DEFAULT_TITLE_SELS);
+        assert_eq!(sels.len(), DEFAULT_TITLE_SELS.len());
+    }
+
+    #[test]
+    fn selectors_or_default_keeps_valid_custom_entries() {
+        let custom = vec!["h1.title".to_string()];
+        let sels = selectors_or_default(&custom, DEFAULT_TITLE_SELS);
+        assert_eq!(sels.len(), 1);
+    }
+}
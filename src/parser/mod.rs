@@ -0,0 +1,67 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use scraper::{ElementRef, Selector};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::marketplace::Marketplace;
+use crate::ratelimit::RequestGate;
+use crate::{Detail, Product};
+
+pub mod amazon;
+
+/// A source-specific scraping strategy.
+///
+/// Each marketplace (Amazon, Fnac, eBay, ...) implements this trait once,
+/// compiling its `Selector`s up front in `new()`. `main` asks each
+/// registered parser whether it can handle the target URL and defers to
+/// the first match.
+#[async_trait]
+pub trait SiteParser: Send + Sync {
+    /// Whether this parser knows how to handle `url`.
+    fn can_parse(&self, url: &Url) -> bool;
+
+    /// Selector matching one search-result card.
+    fn item_selector(&self) -> &Selector;
+
+    fn extract_title(&self, item: &ElementRef) -> Option<String>;
+    fn extract_link(&self, item: &ElementRef) -> Option<String>;
+    fn extract_price(&self, item: &ElementRef) -> Option<String>;
+
+    /// Fetch and parse the product detail page linked from a search result,
+    /// going through `gate` for rate limiting and bounded concurrency.
+    async fn parse_detail(&self, client: &Client, gate: &RequestGate, url: &str) -> Result<Detail>;
+}
+
+/// A `SiteParser` that can also run a keyword search directly, without
+/// crawling a pre-built URL, so `main` can fan a single keyword out across
+/// every configured `Marketplace`.
+#[async_trait]
+pub trait SearchParser: SiteParser {
+    /// Search this marketplace's site for `keyword`, returning up to
+    /// `max_results` matches (title/link/price only — no detail-page fetch).
+    async fn search(
+        &self,
+        client: &Client,
+        gate: &RequestGate,
+        keyword: &str,
+        max_results: usize,
+    ) -> Result<Vec<Product>>;
+}
+
+/// The parsers `main` dispatches across, in priority order, for the
+/// configured `marketplace`. `Arc` so a matched parser can be shared across
+/// the concurrently spawned detail fetch tasks. Selector overrides come from
+/// `config`, keyed by marketplace host.
+pub fn registered(config: &Config, marketplace: Marketplace) -> Vec<Arc<dyn SiteParser>> {
+    vec![Arc::new(amazon::Amazon::new(
+        config.selectors.get(marketplace.host()),
+        marketplace,
+    ))]
+}
+
+/// The `Amazon` parser for `marketplace`, used by cross-marketplace search.
+pub fn amazon_for(config: &Config, marketplace: Marketplace) -> amazon::Amazon {
+    amazon::Amazon::new(config.selectors.get(marketplace.host()), marketplace)
+}
@@ -0,0 +1,109 @@
+use anyhow::{Context, Result};
+use governor::clock::DefaultClock;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter};
+use reqwest::{Client, Response};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+type Limiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+const MAX_RETRIES: u32 = 5;
+
+/// Bounds both the throughput (requests/sec, token-bucket) and the
+/// concurrency (max in-flight requests) of every HTTP call the crawler
+/// makes, whether it's the initial search request or a detail-page fetch.
+///
+/// A single `RequestGate` is shared across all of them, so the crawl never
+/// sends more than `requests_per_second` requests a second or more than
+/// `max_in_flight` at once, regardless of how many tasks are fetching detail
+/// pages concurrently.
+pub struct RequestGate {
+    rate_limiter: Limiter,
+    semaphore: Arc<Semaphore>,
+}
+
+impl RequestGate {
+    pub fn new(requests_per_second: u32, max_in_flight: usize) -> Self {
+        let quota = Quota::per_second(NonZeroU32::new(requests_per_second.max(1)).unwrap());
+        Self {
+            rate_limiter: RateLimiter::direct(quota),
+            semaphore: Arc::new(Semaphore::new(max_in_flight.max(1))),
+        }
+    }
+
+    /// Wait for a rate-limiter cell and a concurrency permit, then `GET url`,
+    /// retrying with exponential backoff on HTTP 429/503 (honoring
+    /// `Retry-After` when the server sends one).
+    pub async fn get(&self, client: &Client, url: &str, accept_language: &str) -> Result<Response> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .context("request semaphore closed")?;
+
+        for attempt in 0..=MAX_RETRIES {
+            self.rate_limiter.until_ready().await;
+
+            let res = client
+                .get(url)
+                .header("Accept-Language", accept_language)
+                .send()
+                .await
+                .with_context(|| format!("request failed: {url}"))?;
+
+            if !matches!(res.status().as_u16(), 429 | 503) || attempt == MAX_RETRIES {
+                return Ok(res);
+            }
+
+            let wait = retry_after(&res).unwrap_or_else(|| backoff(attempt));
+            tokio::time::sleep(wait).await;
+        }
+
+        unreachable!("loop always returns by the final attempt")
+    }
+}
+
+fn retry_after(res: &Response) -> Option<Duration> {
+    res.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(250 * 2u64.pow(attempt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_each_attempt() {
+        assert_eq!(backoff(0), Duration::from_millis(250));
+        assert_eq!(backoff(1), Duration::from_millis(500));
+        assert_eq!(backoff(2), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn retry_after_parses_seconds_header() {
+        let res: Response = http::Response::builder()
+            .header(reqwest::header::RETRY_AFTER, "7")
+            .body(Vec::new())
+            .unwrap()
+            .into();
+        assert_eq!(retry_after(&res), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn retry_after_is_none_without_header() {
+        let res: Response = http::Response::builder().body(Vec::new()).unwrap().into();
+        assert_eq!(retry_after(&res), None);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::config::Config;
+use crate::parser::{self, SearchParser};
+use crate::ratelimit::RequestGate;
+use crate::{parse_price, Product};
+use reqwest::Client;
+
+/// Search `keyword` across every marketplace in `config.search_marketplaces()`
+/// (all four by default), aggregating matches into one list ranked
+/// cheapest-first by USD-converted price so a user can compare a product's
+/// price across Amazon regions in a single invocation. Each `Product` keeps
+/// its source `Marketplace` so the currency it was actually priced in is
+/// never lost.
+pub async fn search_all_marketplaces(
+    client: &Client,
+    gate: &RequestGate,
+    config: &Config,
+    keyword: &str,
+    max_results: usize,
+) -> Vec<Product> {
+    let mut matches = Vec::new();
+    for marketplace in config.search_marketplaces() {
+        let amazon = parser::amazon_for(config, marketplace);
+        match amazon.search(client, gate, keyword, max_results).await {
+            Ok(found) => {
+                println!(
+                    "{:?} ({}): {} matches",
+                    marketplace,
+                    marketplace.currency(),
+                    found.len()
+                );
+                matches.extend(found);
+            }
+            Err(e) => println!("search failed for {:?}: {}", marketplace, e),
+        }
+    }
+
+    // Products without a parsable price go after every priced one.
+    matches.sort_by(|a, b| match (price_in_usd(a), price_in_usd(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    matches
+}
+
+/// `p.price` converted to USD via its marketplace's fixed exchange rate, so
+/// prices from different currencies can be compared directly.
+fn price_in_usd(p: &Product) -> Option<f64> {
+    parse_price(&p.price).map(|price| price * p.marketplace.usd_exchange_rate())
+}
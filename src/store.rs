@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+/// One scraped snapshot of a product, as written to the price-history log.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceRecord {
+    pub title: String,
+    pub link: String,
+    pub price: String,
+    pub rating_text: String,
+    pub review_count: String,
+    pub timestamp: i64, // unix seconds
+}
+
+/// Append-only JSON-lines log of every product snapshot ever scraped,
+/// used to detect price drops between runs.
+pub struct PriceStore {
+    path: PathBuf,
+}
+
+impl PriceStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Price (as scraped, e.g. `"$563.68"`) most recently recorded for `link`,
+    /// or `None` if this product has never been seen before.
+    pub fn last_price(&self, link: &str) -> Result<Option<String>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let file = std::fs::File::open(&self.path).context("open price store failed")?;
+        let mut last = None;
+        for line in BufReader::new(file).lines() {
+            let line = line.context("read price store line failed")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: PriceRecord =
+                serde_json::from_str(&line).context("parse price record failed")?;
+            if record.link == link {
+                last = Some(record.price);
+            }
+        }
+        Ok(last)
+    }
+
+    /// Append a new snapshot to the log.
+    pub fn append(&self, record: &PriceRecord) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("open price store for append failed")?;
+        let line = serde_json::to_string(record).context("serialize price record failed")?;
+        writeln!(file, "{line}").context("write price record failed")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.jsonl", std::process::id()))
+    }
+
+    #[test]
+    fn last_price_is_none_for_unseen_link() {
+        let store = PriceStore::new(temp_path("price-store-unseen"));
+        assert_eq!(store.last_price("https://example.com/dp/1").unwrap(), None);
+    }
+
+    #[test]
+    fn append_then_last_price_returns_most_recent() {
+        let path = temp_path("price-store-roundtrip");
+        let store = PriceStore::new(&path);
+
+        store
+            .append(&PriceRecord {
+                title: "Widget".to_string(),
+                link: "https://example.com/dp/1".to_string(),
+                price: "$10.00".to_string(),
+                rating_text: "N/A".to_string(),
+                review_count: "N/A".to_string(),
+                timestamp: 1,
+            })
+            .unwrap();
+        store
+            .append(&PriceRecord {
+                title: "Widget".to_string(),
+                link: "https://example.com/dp/1".to_string(),
+                price: "$8.00".to_string(),
+                rating_text: "N/A".to_string(),
+                review_count: "N/A".to_string(),
+                timestamp: 2,
+            })
+            .unwrap();
+
+        assert_eq!(
+            store.last_price("https://example.com/dp/1").unwrap(),
+            Some("$8.00".to_string())
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+}